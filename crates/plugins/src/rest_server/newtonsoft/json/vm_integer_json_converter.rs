@@ -2,6 +2,7 @@
 //
 // Rust port of `Neo.Plugins.RestServer.Newtonsoft.Json.VmIntegerJsonConverter`.
 
+use crate::rest_server::rest_server_settings::IntegerJsonMode;
 use crate::rest_server::rest_server_utility::{RestServerUtility, RestServerUtilityError};
 use neo_vm::stack_item::{integer::Integer, StackItem};
 use serde_json::Value;
@@ -9,11 +10,28 @@ use serde_json::Value;
 pub struct VmIntegerJsonConverter;
 
 impl VmIntegerJsonConverter {
+    /// Serialises `value` into a `{ "type": "Integer", "value": ... }` stack
+    /// item object, encoding `value` per the REST server's currently
+    /// configured [`IntegerJsonMode`] (under `Auto`, a JSON number unless the
+    /// value is too large to round-trip safely, in which case a decimal
+    /// string is emitted).
     pub fn to_json(value: &Integer) -> Result<Value, RestServerUtilityError> {
         let stack_item = StackItem::Integer(value.value().clone());
         RestServerUtility::stack_item_to_j_token(&stack_item)
     }
 
+    /// Serialises `value` with an explicit [`IntegerJsonMode`], e.g. to force
+    /// `AlwaysString` for clients that need lossless arbitrary-precision output.
+    pub fn to_json_with_mode(
+        value: &Integer,
+        mode: IntegerJsonMode,
+    ) -> Result<Value, RestServerUtilityError> {
+        let stack_item = StackItem::Integer(value.value().clone());
+        RestServerUtility::stack_item_to_j_token_with_mode(&stack_item, mode)
+    }
+
+    /// Parses an `Integer` stack item object whose `value` is either a JSON
+    /// number or a decimal string back into an `Integer`.
     pub fn from_json(token: &Value) -> Result<Integer, RestServerUtilityError> {
         match RestServerUtility::stack_item_from_j_token(token)? {
             StackItem::Integer(value) => Ok(Integer::new(value)),
@@ -24,3 +42,33 @@ impl VmIntegerJsonConverter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    // `Auto` is the crate default; this locks in the documented behavior
+    // change from the converter's old always-a-string output.
+    #[test]
+    fn auto_mode_emits_small_integers_as_json_numbers() {
+        let value = Integer::new(42);
+        let json = VmIntegerJsonConverter::to_json_with_mode(&value, IntegerJsonMode::Auto).unwrap();
+        assert!(json["value"].is_number());
+    }
+
+    #[test]
+    fn auto_mode_falls_back_to_string_beyond_safe_range() {
+        let value = Integer::new(BigInt::from(9_007_199_254_740_991_i64) + 1);
+        let json = VmIntegerJsonConverter::to_json_with_mode(&value, IntegerJsonMode::Auto).unwrap();
+        assert!(json["value"].is_string());
+    }
+
+    #[test]
+    fn always_string_mode_preserves_prior_behavior() {
+        let value = Integer::new(42);
+        let json =
+            VmIntegerJsonConverter::to_json_with_mode(&value, IntegerJsonMode::AlwaysString).unwrap();
+        assert_eq!(json["value"], Value::String("42".to_string()));
+    }
+}
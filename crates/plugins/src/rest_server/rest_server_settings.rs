@@ -52,6 +52,47 @@ impl Default for MissingMemberHandling {
     }
 }
 
+/// Controls how the VM `Integer` JSON converter encodes `BigInteger` values.
+///
+/// Plain JSON numbers only round-trip safely up to the JS/JSON safe integer
+/// range (`+/-2^53-1`); beyond that, consumers that decode JSON into a
+/// double-precision number silently lose precision.
+///
+/// Note this is a wire-format behavior change: previously every `Integer`
+/// stack item was always emitted as a decimal string, regardless of size.
+/// With `Auto` as the default, small integers now serialize as JSON
+/// *numbers* instead. Deployments with consumers/snapshots that assume the
+/// old always-a-string form should set `IntegerJsonMode: "AlwaysString"` in
+/// config to keep the previous behavior.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum IntegerJsonMode {
+    /// Emit a JSON number for values within the safe integer range and fall
+    /// back to a decimal string for anything larger. Default.
+    Auto,
+    /// Always emit a decimal string, guaranteeing a lossless round-trip for
+    /// the full `BigInteger` range the VM supports. Matches the converter's
+    /// pre-existing behavior.
+    AlwaysString,
+}
+
+impl Default for IntegerJsonMode {
+    fn default() -> Self {
+        IntegerJsonMode::Auto
+    }
+}
+
+impl FromStr for IntegerJsonMode {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "Auto" => Ok(IntegerJsonMode::Auto),
+            "AlwaysString" => Ok(IntegerJsonMode::AlwaysString),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Behaviour for serialising `null` values.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum NullValueHandling {
@@ -225,6 +266,7 @@ pub struct RestServerSettings {
     pub rate_limit_window_seconds: i32,
     pub rate_limit_queue_limit: i32,
     pub json_serializer_settings: JsonSerializerSettings,
+    pub integer_json_mode: IntegerJsonMode,
 }
 
 impl Default for RestServerSettings {
@@ -255,6 +297,7 @@ impl Default for RestServerSettings {
             rate_limit_window_seconds: 60,
             rate_limit_queue_limit: 0,
             json_serializer_settings: JsonSerializerSettings::default(),
+            integer_json_mode: IntegerJsonMode::default(),
         }
     }
 }
@@ -405,6 +448,12 @@ impl RestServerSettings {
             result.rate_limit_queue_limit = queue as i32;
         }
 
+        if let Some(mode) = config.get("IntegerJsonMode").and_then(Value::as_str) {
+            if let Ok(parsed) = IntegerJsonMode::from_str(mode) {
+                result.integer_json_mode = parsed;
+            }
+        }
+
         result
     }
 }
@@ -5,6 +5,7 @@
 // the UtilsController; additional helpers will be added as the remaining
 // controllers are ported.
 
+use crate::rest_server::rest_server_settings::{IntegerJsonMode, RestServerSettings};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use neo_core::neo_system::ProtocolSettings;
 use neo_core::wallets::helper::Helper as WalletHelper;
@@ -12,12 +13,17 @@ use neo_core::UInt160;
 use neo_vm::script::Script;
 use neo_vm::stack_item::{StackItem, StackItemType};
 use num_bigint::BigInt;
+use num_traits::ToPrimitive;
 use once_cell::sync::Lazy;
 use serde_json::{Map as JsonMap, Value};
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use thiserror::Error;
 
+/// Largest magnitude that still round-trips losslessly through a JSON
+/// number decoded as an IEEE-754 double (the JS/JSON safe integer range).
+const JSON_SAFE_INTEGER_LIMIT: i64 = 9_007_199_254_740_991;
+
 #[derive(Debug, Error)]
 pub enum RestServerUtilityError {
     #[error("Invalid address format: {0}")]
@@ -58,9 +64,20 @@ impl RestServerUtility {
     }
 
     /// Serialises a VM [`StackItem`] into the JSON structure used by the C# converter.
+    ///
+    /// Uses the REST server's currently configured [`IntegerJsonMode`]; call
+    /// [`Self::stack_item_to_j_token_with_mode`] to pick a mode explicitly.
     pub fn stack_item_to_j_token(item: &StackItem) -> Result<Value, RestServerUtilityError> {
+        Self::stack_item_to_j_token_with_mode(item, RestServerSettings::current().integer_json_mode)
+    }
+
+    /// Serialises a VM [`StackItem`] into JSON, encoding `Integer` values per `mode`.
+    pub fn stack_item_to_j_token_with_mode(
+        item: &StackItem,
+        mode: IntegerJsonMode,
+    ) -> Result<Value, RestServerUtilityError> {
         let mut context = Vec::new();
-        Self::stack_item_to_j_token_internal(item, &mut context)
+        Self::stack_item_to_j_token_internal(item, &mut context, mode)
     }
 
     /// Deserialises a JSON token into a VM [`StackItem`] (inverse of [`stack_item_to_j_token`]).
@@ -68,9 +85,24 @@ impl RestServerUtility {
         Self::stack_item_from_j_token_internal(token)
     }
 
+    /// Encodes a `BigInteger` value per the given [`IntegerJsonMode`], falling
+    /// back to a decimal string whenever the value would otherwise overflow
+    /// the JSON/JS safe integer range.
+    fn integer_to_j_token(value: &BigInt, mode: IntegerJsonMode) -> Value {
+        if mode == IntegerJsonMode::Auto {
+            if let Some(small) = value.to_i64() {
+                if (-JSON_SAFE_INTEGER_LIMIT..=JSON_SAFE_INTEGER_LIMIT).contains(&small) {
+                    return Value::Number(small.into());
+                }
+            }
+        }
+        Value::String(value.to_string())
+    }
+
     fn stack_item_to_j_token_internal(
         item: &StackItem,
         context: &mut Vec<*const StackItem>,
+        mode: IntegerJsonMode,
     ) -> Result<Value, RestServerUtilityError> {
         let ptr = item as *const StackItem;
         if context.iter().any(|existing| *existing == ptr) {
@@ -91,7 +123,7 @@ impl RestServerUtility {
             })),
             StackItem::Integer(value) => Ok(serde_json::json!({
                 "type": format!("{:?}", StackItemType::Integer),
-                "value": value.to_string(),
+                "value": Self::integer_to_j_token(value, mode),
             })),
             StackItem::ByteString(bytes) => Ok(serde_json::json!({
                 "type": format!("{:?}", StackItemType::ByteString),
@@ -109,7 +141,7 @@ impl RestServerUtility {
                 context.push(ptr);
                 let mut values = Vec::with_capacity(array.len());
                 for entry in array.items() {
-                    values.push(Self::stack_item_to_j_token_internal(entry, context)?);
+                    values.push(Self::stack_item_to_j_token_internal(entry, context, mode)?);
                 }
                 context.retain(|existing| *existing != ptr);
                 Ok(serde_json::json!({
@@ -121,7 +153,7 @@ impl RestServerUtility {
                 context.push(ptr);
                 let mut values = Vec::with_capacity(structure.len());
                 for entry in structure.items() {
-                    values.push(Self::stack_item_to_j_token_internal(entry, context)?);
+                    values.push(Self::stack_item_to_j_token_internal(entry, context, mode)?);
                 }
                 context.retain(|existing| *existing != ptr);
                 Ok(serde_json::json!({
@@ -133,8 +165,8 @@ impl RestServerUtility {
                 context.push(ptr);
                 let mut entries = Vec::with_capacity(map.len());
                 for (key, value) in map.items() {
-                    let key_json = Self::stack_item_to_j_token_internal(key, context)?;
-                    let value_json = Self::stack_item_to_j_token_internal(value, context)?;
+                    let key_json = Self::stack_item_to_j_token_internal(key, context, mode)?;
+                    let value_json = Self::stack_item_to_j_token_internal(value, context, mode)?;
                     entries.push(serde_json::json!({
                         "key": key_json,
                         "value": value_json,
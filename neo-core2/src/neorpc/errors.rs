@@ -143,6 +143,16 @@ impl Error {
     pub fn wrap_with_data(&self, data: Option<String>) -> Self {
         Error::new(self.code, &self.message, data)
     }
+
+    /// The JSON-RPC error code.
+    pub fn code(&self) -> i64 {
+        self.code
+    }
+
+    /// The `data` member, if any.
+    pub fn data(&self) -> Option<&str> {
+        self.data.as_deref()
+    }
 }
 
 impl fmt::Display for Error {
@@ -13,6 +13,10 @@ pub enum EventID {
     ExecutionEventID,
     NotaryRequestEventID,
     HeaderOfAddedBlockEventID,
+    /// Synthetic event emitted by a subscription feed after a
+    /// [`crate::neorpc::HeartbeatFilter`] interval of silence, so a
+    /// subscriber can tell "quiet" from "dead".
+    HeartbeatEventID,
     MissedEventID = 255,
 }
 
@@ -25,6 +29,7 @@ impl fmt::Display for EventID {
             EventID::ExecutionEventID => "transaction_executed",
             EventID::NotaryRequestEventID => "notary_request_event",
             EventID::HeaderOfAddedBlockEventID => "header_of_added_block",
+            EventID::HeartbeatEventID => "heartbeat",
             EventID::MissedEventID => "event_missed",
             _ => "unknown",
         };
@@ -43,6 +48,7 @@ impl FromStr for EventID {
             "transaction_executed" => Ok(EventID::ExecutionEventID),
             "notary_request_event" => Ok(EventID::NotaryRequestEventID),
             "header_of_added_block" => Ok(EventID::HeaderOfAddedBlockEventID),
+            "heartbeat" => Ok(EventID::HeartbeatEventID),
             "event_missed" => Ok(EventID::MissedEventID),
             _ => Err("invalid stream name".to_string()),
         }
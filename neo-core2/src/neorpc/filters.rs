@@ -1,57 +1,165 @@
 use std::error::Error;
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
+use crate::core::block;
 use crate::core::interop::runtime;
 use crate::core::mempoolevent;
+use crate::core::state;
+use crate::core::transaction;
+use crate::neorpc::result::NotaryRequestEvent;
 use crate::util;
 use crate::vm::vmstate;
 
-#[derive(Clone, Debug)]
+/// Field names below match the Neo JSON-RPC `subscribe` params so the
+/// filters can be parsed directly out of an incoming request and, in CBOR
+/// form, persisted to disk so in-flight subscriptions survive a restart.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BlockFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub primary: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub since: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub till: Option<u32>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sender: Option<util::Uint160>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub signer: Option<util::Uint160>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NotificationFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub contract: Option<util::Uint160>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecutionFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub container: Option<util::Uint256>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NotaryRequestFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sender: Option<util::Uint160>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub signer: Option<util::Uint160>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub type_: Option<mempoolevent::Type>,
 }
 
+/// Synthetic filter that doesn't match real chain events at all; instead it
+/// asks the subscription feed to emit an idle heartbeat notification once
+/// `interval_ms` has elapsed without a genuine delivery, JetStream-style.
+/// The timer is re-armed relative to the *last delivered message* rather
+/// than a fixed wall-clock schedule, so a busy feed never gets spurious
+/// heartbeats and a quiet one is distinguishable from a dead one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeartbeatFilter {
+    pub interval_ms: u32,
+}
+
 pub trait SubscriptionFilter {
-    fn is_valid(&self) -> Result<(), Box<dyn Error>>;
+    /// The kind of event this filter is matched against.
+    type Event;
+
+    fn is_valid(&self) -> Result<(), SubscriptionFilterError>;
+
+    /// Reports whether `event` satisfies this filter. A `None` field is
+    /// always treated as a wildcard that matches any value.
+    fn matches(&self, event: &Self::Event) -> bool;
 }
 
-#[derive(Debug, Clone)]
-pub struct InvalidSubscriptionFilter;
+/// Machine-readable validation failure for a [`SubscriptionFilter`], so
+/// callers can match on the specific cause instead of string-scraping a
+/// formatted message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionFilterError {
+    /// `NotificationFilter.name` is longer than the runtime allows.
+    NameTooLong { len: usize, max: usize },
+    /// `ExecutionFilter.state` isn't one of the VM's terminal states.
+    InvalidExecutionState { got: String, expected: String },
+    /// A NATS-style wildcard pattern is malformed, e.g. `>` used anywhere
+    /// but the last dot-separated token.
+    InvalidWildcard { pattern: String },
+    /// `HeartbeatFilter.interval_ms` is below [`MIN_HEARTBEAT_INTERVAL_MS`].
+    IntervalTooShort { interval_ms: u32, min: u32 },
+}
 
-impl fmt::Display for InvalidSubscriptionFilter {
+impl fmt::Display for SubscriptionFilterError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "invalid subscription filter")
+        match self {
+            SubscriptionFilterError::NameTooLong { len, max } => write!(
+                f,
+                "NotificationFilter name parameter must be at most {max} (got {len})"
+            ),
+            SubscriptionFilterError::InvalidExecutionState { got, expected } => write!(
+                f,
+                "ExecutionFilter state parameter must be either {expected} (got {got})"
+            ),
+            SubscriptionFilterError::InvalidWildcard { pattern } => write!(
+                f,
+                "NotificationFilter name parameter '{pattern}' has '{NAME_WILDCARD_REST}' in a non-final position"
+            ),
+            SubscriptionFilterError::IntervalTooShort { interval_ms, min } => write!(
+                f,
+                "HeartbeatFilter interval_ms must be at least {min} (got {interval_ms})"
+            ),
+        }
     }
 }
 
-impl Error for InvalidSubscriptionFilter {}
+impl Error for SubscriptionFilterError {}
+
+impl SubscriptionFilterError {
+    /// Maps this error onto a JSON-RPC "Invalid params" error, with `data`
+    /// carrying the variant's fields as a JSON object (tagged by `reason`)
+    /// rather than just the formatted [`Display`] message, so callers can
+    /// switch on the specific cause instead of string-scraping it.
+    pub fn to_rpc_error(&self) -> crate::neorpc::Error {
+        crate::neorpc::Error::new_invalid_params_error(Some(self.to_data_json()))
+    }
+
+    fn to_data_json(&self) -> String {
+        let data = match self {
+            SubscriptionFilterError::NameTooLong { len, max } => serde_json::json!({
+                "reason": "name_too_long",
+                "message": self.to_string(),
+                "len": len,
+                "max": max,
+            }),
+            SubscriptionFilterError::InvalidExecutionState { got, expected } => serde_json::json!({
+                "reason": "invalid_execution_state",
+                "message": self.to_string(),
+                "got": got,
+                "expected": expected,
+            }),
+            SubscriptionFilterError::InvalidWildcard { pattern } => serde_json::json!({
+                "reason": "invalid_wildcard",
+                "message": self.to_string(),
+                "pattern": pattern,
+            }),
+            SubscriptionFilterError::IntervalTooShort { interval_ms, min } => serde_json::json!({
+                "reason": "interval_too_short",
+                "message": self.to_string(),
+                "interval_ms": interval_ms,
+                "min": min,
+            }),
+        };
+        data.to_string()
+    }
+}
 
 impl BlockFilter {
     pub fn copy(&self) -> BlockFilter {
@@ -64,9 +172,17 @@ impl BlockFilter {
 }
 
 impl SubscriptionFilter for BlockFilter {
-    fn is_valid(&self) -> Result<(), Box<dyn Error>> {
+    type Event = block::Header;
+
+    fn is_valid(&self) -> Result<(), SubscriptionFilterError> {
         Ok(())
     }
+
+    fn matches(&self, header: &Self::Event) -> bool {
+        self.primary.map_or(true, |p| p == header.primary_index)
+            && self.since.map_or(true, |s| s <= header.index)
+            && self.till.map_or(true, |t| header.index <= t)
+    }
 }
 
 impl TxFilter {
@@ -79,11 +195,27 @@ impl TxFilter {
 }
 
 impl SubscriptionFilter for TxFilter {
-    fn is_valid(&self) -> Result<(), Box<dyn Error>> {
+    type Event = transaction::Transaction;
+
+    fn is_valid(&self) -> Result<(), SubscriptionFilterError> {
         Ok(())
     }
+
+    fn matches(&self, tx: &Self::Event) -> bool {
+        let sender_ok = self.sender.map_or(true, |s| tx.sender().equals(&s));
+        let signer_ok = self.signer.map_or(true, |s| {
+            tx.signers.iter().any(|signer| signer.account.equals(&s))
+        });
+        sender_ok && signer_ok
+    }
 }
 
+/// Token that matches exactly one name segment, NATS-subject style.
+const NAME_WILDCARD_ONE: &str = "*";
+/// Token that matches one or more trailing name segments. Only valid as
+/// the final token of a pattern.
+const NAME_WILDCARD_REST: &str = ">";
+
 impl NotificationFilter {
     pub fn copy(&self) -> NotificationFilter {
         NotificationFilter {
@@ -91,23 +223,57 @@ impl NotificationFilter {
             name: self.name.clone(),
         }
     }
+
+    /// Reports whether the dot-separated `name` matches `pattern`, where
+    /// `pattern` may use `*` to match exactly one token and `>` to match
+    /// the remainder of the tokens (only valid in the final position).
+    fn name_matches(pattern: &str, name: &str) -> bool {
+        let mut pattern_tokens = pattern.split('.');
+        let mut name_tokens = name.split('.');
+        loop {
+            return match (pattern_tokens.next(), name_tokens.next()) {
+                (Some(NAME_WILDCARD_REST), Some(_)) => true,
+                (Some(NAME_WILDCARD_ONE), Some(_)) => continue,
+                (Some(p), Some(n)) if p == n => continue,
+                (None, None) => true,
+                _ => false,
+            };
+        }
+    }
 }
 
 impl SubscriptionFilter for NotificationFilter {
-    fn is_valid(&self) -> Result<(), Box<dyn Error>> {
+    type Event = state::ContainedNotificationEvent;
+
+    fn is_valid(&self) -> Result<(), SubscriptionFilterError> {
         if let Some(name) = &self.name {
             if name.len() > runtime::MAX_EVENT_NAME_LEN {
-                return Err(Box::new(fmt::Error::new(
-                    fmt::ErrorKind::InvalidInput,
-                    format!(
-                        "NotificationFilter name parameter must be less than {}",
-                        runtime::MAX_EVENT_NAME_LEN
-                    ),
-                )));
+                return Err(SubscriptionFilterError::NameTooLong {
+                    len: name.len(),
+                    max: runtime::MAX_EVENT_NAME_LEN,
+                });
+            }
+            let tokens: Vec<&str> = name.split('.').collect();
+            if let Some(pos) = tokens.iter().position(|t| *t == NAME_WILDCARD_REST) {
+                if pos != tokens.len() - 1 {
+                    return Err(SubscriptionFilterError::InvalidWildcard {
+                        pattern: name.clone(),
+                    });
+                }
             }
         }
         Ok(())
     }
+
+    fn matches(&self, event: &Self::Event) -> bool {
+        let contract_ok = self
+            .contract
+            .map_or(true, |c| event.notification_event.script_hash.equals(&c));
+        let name_ok = self.name.as_ref().map_or(true, |pattern| {
+            Self::name_matches(pattern, &event.notification_event.name)
+        });
+        contract_ok && name_ok
+    }
 }
 
 impl ExecutionFilter {
@@ -120,20 +286,28 @@ impl ExecutionFilter {
 }
 
 impl SubscriptionFilter for ExecutionFilter {
-    fn is_valid(&self) -> Result<(), Box<dyn Error>> {
+    type Event = state::AppExecResult;
+
+    fn is_valid(&self) -> Result<(), SubscriptionFilterError> {
         if let Some(state) = &self.state {
             if state != &vmstate::HALT.to_string() && state != &vmstate::FAULT.to_string() {
-                return Err(Box::new(fmt::Error::new(
-                    fmt::ErrorKind::InvalidInput,
-                    format!(
-                        "ExecutionFilter state parameter must be either {} or {}",
-                        vmstate::HALT, vmstate::FAULT
-                    ),
-                )));
+                return Err(SubscriptionFilterError::InvalidExecutionState {
+                    got: state.clone(),
+                    expected: format!("{} or {}", vmstate::HALT, vmstate::FAULT),
+                });
             }
         }
         Ok(())
     }
+
+    fn matches(&self, event: &Self::Event) -> bool {
+        let state_ok = self
+            .state
+            .as_ref()
+            .map_or(true, |s| event.vm_state.to_string() == *s);
+        let container_ok = self.container.map_or(true, |c| event.container.equals(&c));
+        state_ok && container_ok
+    }
 }
 
 impl NotaryRequestFilter {
@@ -147,7 +321,103 @@ impl NotaryRequestFilter {
 }
 
 impl SubscriptionFilter for NotaryRequestFilter {
-    fn is_valid(&self) -> Result<(), Box<dyn Error>> {
+    type Event = NotaryRequestEvent;
+
+    fn is_valid(&self) -> Result<(), SubscriptionFilterError> {
+        Ok(())
+    }
+
+    fn matches(&self, event: &Self::Event) -> bool {
+        let type_ok = self.type_.map_or(true, |t| event.type_ == t);
+        let Some(request) = &event.notary_request else {
+            return type_ok && self.sender.is_none() && self.signer.is_none();
+        };
+        let sender_ok = self.sender.map_or(true, |s| {
+            request
+                .fallback_transaction
+                .signers
+                .get(1)
+                .is_some_and(|signer| signer.account.equals(&s))
+        });
+        let signer_ok = self.signer.map_or(true, |s| {
+            request
+                .main_transaction
+                .signers
+                .iter()
+                .any(|signer| signer.account.equals(&s))
+        });
+        sender_ok && signer_ok && type_ok
+    }
+}
+
+/// `HeartbeatFilter.interval_ms` below this is rejected: it exists to stop
+/// subscribers from turning the heartbeat into a de facto polling loop.
+const MIN_HEARTBEAT_INTERVAL_MS: u32 = 1000;
+
+impl HeartbeatFilter {
+    pub fn copy(&self) -> HeartbeatFilter {
+        HeartbeatFilter {
+            interval_ms: self.interval_ms,
+        }
+    }
+}
+
+impl SubscriptionFilter for HeartbeatFilter {
+    /// Time elapsed since the feed last delivered a message to this
+    /// subscriber.
+    type Event = std::time::Duration;
+
+    fn is_valid(&self) -> Result<(), SubscriptionFilterError> {
+        if self.interval_ms < MIN_HEARTBEAT_INTERVAL_MS {
+            return Err(SubscriptionFilterError::IntervalTooShort {
+                interval_ms: self.interval_ms,
+                min: MIN_HEARTBEAT_INTERVAL_MS,
+            });
+        }
         Ok(())
     }
+
+    /// Reports whether `idle_for` has reached this filter's interval, i.e.
+    /// whether a heartbeat is due. Callers re-arm the timer from the most
+    /// recent delivery (real or heartbeat), not from a fixed wall-clock
+    /// schedule.
+    fn matches(&self, idle_for: &Self::Event) -> bool {
+        idle_for.as_millis() >= self.interval_ms as u128
+    }
+}
+
+/// Per-subscriber idle tracker that turns a [`HeartbeatFilter`] into actual
+/// emission decisions. A subscription feed calls [`Self::record_delivery`]
+/// every time it sends the subscriber a real message, and polls
+/// [`Self::poll`] on its own schedule; when `poll` returns `true` the caller
+/// sends a synthetic heartbeat and the timer is re-armed from that instant,
+/// exactly as it would be for a genuine delivery.
+pub struct HeartbeatTimer {
+    filter: HeartbeatFilter,
+    last_delivery: std::time::Instant,
+}
+
+impl HeartbeatTimer {
+    pub fn new(filter: HeartbeatFilter) -> Self {
+        HeartbeatTimer {
+            filter,
+            last_delivery: std::time::Instant::now(),
+        }
+    }
+
+    /// Re-arms the idle window from now. Call this after every delivery,
+    /// real or synthetic.
+    pub fn record_delivery(&mut self) {
+        self.last_delivery = std::time::Instant::now();
+    }
+
+    /// Reports whether a heartbeat is due and, if so, re-arms the timer.
+    pub fn poll(&mut self) -> bool {
+        if self.filter.matches(&self.last_delivery.elapsed()) {
+            self.record_delivery();
+            true
+        } else {
+            false
+        }
+    }
 }
@@ -1,7 +1,10 @@
-use crate::neorpc::{BlockFilter, TxFilter, NotificationFilter, ExecutionFilter};
+use crate::core::mempoolevent::Type as MempoolEventType;
+use crate::neorpc::{BlockFilter, TxFilter, NotificationFilter, ExecutionFilter, NotaryRequestFilter, HeartbeatFilter, HeartbeatTimer, SubscriptionFilter};
 use crate::util::{Uint160, Uint256};
+use serde::{Deserialize, Serialize};
 use std::ptr;
 use std::mem;
+use std::time::Duration;
 
 #[test]
 fn test_block_filter_copy() {
@@ -113,3 +116,164 @@ fn test_execution_filter_copy() {
     bf.as_mut().unwrap().container = Some(Uint256::from([3, 2, 1]));
     assert_ne!(bf, tf);
 }
+
+fn json_roundtrip<T>(filter: &T)
+where
+    T: Serialize + for<'de> Deserialize<'de> + PartialEq + std::fmt::Debug,
+{
+    let encoded = serde_json::to_string(filter).unwrap();
+    let decoded: T = serde_json::from_str(&encoded).unwrap();
+    assert_eq!(filter, &decoded);
+}
+
+fn cbor_roundtrip<T>(filter: &T)
+where
+    T: Serialize + for<'de> Deserialize<'de> + PartialEq + std::fmt::Debug,
+{
+    let encoded = serde_cbor::to_vec(filter).unwrap();
+    let decoded: T = serde_cbor::from_slice(&encoded).unwrap();
+    assert_eq!(filter, &decoded);
+}
+
+#[test]
+fn test_block_filter_serde_roundtrip() {
+    let bf = BlockFilter {
+        primary: Some(1),
+        since: Some(2),
+        till: Some(3),
+    };
+    json_roundtrip(&bf);
+    cbor_roundtrip(&bf);
+}
+
+#[test]
+fn test_tx_filter_serde_roundtrip() {
+    let tf = TxFilter {
+        sender: Some(Uint160::from([1, 2, 3])),
+        signer: Some(Uint160::from([3, 2, 1])),
+    };
+    json_roundtrip(&tf);
+    cbor_roundtrip(&tf);
+}
+
+#[test]
+fn test_notification_filter_serde_roundtrip() {
+    let nf = NotificationFilter {
+        contract: Some(Uint160::from([1, 2, 3])),
+        name: Some("foo.bar".to_string()),
+    };
+    json_roundtrip(&nf);
+    cbor_roundtrip(&nf);
+}
+
+#[test]
+fn test_execution_filter_serde_roundtrip() {
+    let ef = ExecutionFilter {
+        state: Some("HALT".to_string()),
+        container: Some(Uint256::from([1, 2, 3])),
+    };
+    json_roundtrip(&ef);
+    cbor_roundtrip(&ef);
+}
+
+#[test]
+fn test_notary_request_filter_serde_roundtrip() {
+    let nrf = NotaryRequestFilter {
+        sender: Some(Uint160::from([1, 2, 3])),
+        signer: Some(Uint160::from([3, 2, 1])),
+        type_: Some(MempoolEventType::TransactionAdded),
+    };
+    json_roundtrip(&nrf);
+    cbor_roundtrip(&nrf);
+}
+
+#[test]
+fn test_notary_request_filter_json_field_name() {
+    let nrf = NotaryRequestFilter {
+        sender: None,
+        signer: None,
+        type_: Some(MempoolEventType::TransactionAdded),
+    };
+    let encoded = serde_json::to_value(&nrf).unwrap();
+    assert!(encoded.get("type").is_some());
+    assert!(encoded.get("type_").is_none());
+}
+
+#[test]
+fn test_filter_uint_fields_use_canonical_hex_form() {
+    let tf = TxFilter {
+        sender: Some(Uint160::from([1, 2, 3])),
+        signer: None,
+    };
+    let encoded = serde_json::to_value(&tf).unwrap();
+    let sender = encoded.get("sender").unwrap().as_str().unwrap();
+    assert!(sender.starts_with("0x"));
+
+    let ef = ExecutionFilter {
+        state: None,
+        container: Some(Uint256::from([1, 2, 3])),
+    };
+    let encoded = serde_json::to_value(&ef).unwrap();
+    let container = encoded.get("container").unwrap().as_str().unwrap();
+    assert!(container.starts_with("0x"));
+}
+
+#[test]
+fn test_heartbeat_filter_copy() {
+    let bf = HeartbeatFilter { interval_ms: 5000 };
+    let tf = bf.copy();
+    assert_eq!(bf, tf);
+}
+
+#[test]
+fn test_heartbeat_filter_is_valid() {
+    assert!(HeartbeatFilter { interval_ms: 999 }.is_valid().is_err());
+    assert!(HeartbeatFilter { interval_ms: 1000 }.is_valid().is_ok());
+    assert!(HeartbeatFilter { interval_ms: 5000 }.is_valid().is_ok());
+}
+
+#[test]
+fn test_heartbeat_filter_matches() {
+    let filter = HeartbeatFilter { interval_ms: 5000 };
+    assert!(!filter.matches(&Duration::from_millis(4999)));
+    assert!(filter.matches(&Duration::from_millis(5000)));
+    assert!(filter.matches(&Duration::from_secs(10)));
+}
+
+#[test]
+fn test_heartbeat_filter_serde_roundtrip() {
+    let hf = HeartbeatFilter { interval_ms: 15000 };
+    json_roundtrip(&hf);
+    cbor_roundtrip(&hf);
+}
+
+#[test]
+fn test_heartbeat_timer_polls_after_interval_and_rearms() {
+    let mut timer = HeartbeatTimer::new(HeartbeatFilter { interval_ms: 1000 });
+    assert!(!timer.poll());
+    std::thread::sleep(Duration::from_millis(1050));
+    assert!(timer.poll());
+    // Just re-armed, so immediately polling again should not fire.
+    assert!(!timer.poll());
+}
+
+#[test]
+fn test_heartbeat_timer_record_delivery_rearms() {
+    let mut timer = HeartbeatTimer::new(HeartbeatFilter { interval_ms: 1000 });
+    std::thread::sleep(Duration::from_millis(1050));
+    timer.record_delivery();
+    assert!(!timer.poll());
+}
+
+#[test]
+fn test_subscription_filter_error_to_rpc_error_has_structured_data() {
+    use crate::neorpc::SubscriptionFilterError;
+
+    let err = SubscriptionFilterError::NameTooLong { len: 300, max: 256 };
+    let rpc_err = err.to_rpc_error();
+    let data = rpc_err.data().expect("data should be set");
+    let parsed: serde_json::Value = serde_json::from_str(data).expect("data should be JSON");
+    assert_eq!(parsed["reason"], "name_too_long");
+    assert_eq!(parsed["len"], 300);
+    assert_eq!(parsed["max"], 256);
+}
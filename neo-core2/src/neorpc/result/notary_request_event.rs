@@ -7,7 +7,7 @@ use crate::network::payload;
 #[derive(Serialize, Deserialize)]
 pub struct NotaryRequestEvent {
     #[serde(rename = "type")]
-    type_: mempoolevent::Type,
+    pub(crate) type_: mempoolevent::Type,
     #[serde(rename = "notaryrequest")]
-    notary_request: Option<payload::P2PNotaryRequest>,
+    pub(crate) notary_request: Option<payload::P2PNotaryRequest>,
 }
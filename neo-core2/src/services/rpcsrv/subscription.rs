@@ -1,6 +1,10 @@
+use std::any::Any;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 use tungstenite::Message as WebSocketMessage;
-use crate::neorpc::{self, EventID, Notification, SubscriptionFilter};
+use crate::neorpc::{EventID, HeartbeatFilter, HeartbeatTimer, Notification};
 
 // intEvent is an internal event that has both a proper structure and
 // a websocket-ready message. It's used to serve websocket-based clients
@@ -19,23 +23,94 @@ struct Subscriber {
     // pointing to an EventID is an obvious overkill at the moment, but
     // that's not for long.
     feeds: [Feed; MAX_FEEDS],
+    // Set once the subscriber asks for idle heartbeats; re-armed from the
+    // last delivery (real or heartbeat) rather than a fixed schedule. Shared
+    // with the background thread `set_heartbeat` spawns, hence the mutex.
+    heartbeat: Arc<Mutex<Option<HeartbeatTimer>>>,
+}
+
+/// How often the background heartbeat thread checks whether a subscriber's
+/// `HeartbeatFilter` interval has elapsed. This is polling granularity, not
+/// the heartbeat interval itself.
+const HEARTBEAT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+impl Subscriber {
+    fn new(writer: crossbeam_channel::Sender<IntEvent>) -> Self {
+        Subscriber {
+            writer,
+            overflown: AtomicBool::new(false),
+            feeds: std::array::from_fn(|_| Feed::empty()),
+            heartbeat: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Sends `ntf` to the subscriber and re-arms the heartbeat timer, if
+    /// any, from this delivery, so a busy feed never also gets spurious
+    /// heartbeats. This is the one place real notifications reach the
+    /// subscriber and is where `HeartbeatTimer::record_delivery` gets
+    /// called from.
+    fn deliver(&self, ntf: Notification) -> Result<(), crossbeam_channel::SendError<IntEvent>> {
+        if let Some(hb) = self.heartbeat.lock().unwrap().as_mut() {
+            hb.record_delivery();
+        }
+        let msg = WebSocketMessage::Text(serde_json::to_string(&ntf).unwrap_or_default());
+        self.writer.send(IntEvent { msg, ntf })
+    }
+
+    /// Registers (or replaces) this subscriber's heartbeat filter and spawns
+    /// the background thread that actually emits a synthetic heartbeat once
+    /// `interval_ms` of silence has elapsed since the last [`Self::deliver`].
+    /// The thread exits on its own once `writer` is closed (the subscriber
+    /// disconnected), so no explicit shutdown signal is needed.
+    fn set_heartbeat(&self, filter: HeartbeatFilter) {
+        *self.heartbeat.lock().unwrap() = Some(HeartbeatTimer::new(filter));
+        let heartbeat = self.heartbeat.clone();
+        let writer = self.writer.clone();
+        thread::spawn(move || loop {
+            thread::sleep(HEARTBEAT_POLL_INTERVAL);
+            let due = heartbeat
+                .lock()
+                .unwrap()
+                .as_mut()
+                .map_or(false, HeartbeatTimer::poll);
+            if !due {
+                continue;
+            }
+            let ntf = Notification {
+                jsonrpc: "2.0".to_string(),
+                method: EventID::HeartbeatEventID,
+                params: Vec::new(),
+            };
+            let msg = WebSocketMessage::Text(serde_json::to_string(&ntf).unwrap_or_default());
+            if writer.send(IntEvent { msg, ntf }).is_err() {
+                return;
+            }
+        });
+    }
 }
 
 // feed stores subscriber's desired event ID with filter.
 struct Feed {
     event: EventID,
-    filter: SubscriptionFilter,
+    filter: Option<Box<dyn Any + Send>>,
 }
 
 impl Feed {
+    fn empty() -> Self {
+        Feed {
+            event: EventID::InvalidEventID,
+            filter: None,
+        }
+    }
+
     // EventID implements neorpc::EventComparator trait and returns notification ID.
     fn event_id(&self) -> EventID {
         self.event
     }
 
     // Filter implements neorpc::EventComparator trait and returns notification filter.
-    fn filter(&self) -> &SubscriptionFilter {
-        &self.filter
+    fn filter(&self) -> Option<&(dyn Any + Send)> {
+        self.filter.as_deref()
     }
 }
 
@@ -50,3 +125,49 @@ const MAX_FEEDS: usize = 16;
 // time, this channel is about sending pointers, so it's doesn't cost
 // a lot in terms of memory used.
 const NOTIFICATION_BUF_SIZE: usize = 1024;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration as TestDuration;
+
+    #[test]
+    fn set_heartbeat_emits_after_the_configured_interval() {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let sub = Subscriber::new(tx);
+        sub.set_heartbeat(HeartbeatFilter { interval_ms: 1000 });
+
+        let evt = rx
+            .recv_timeout(TestDuration::from_millis(1500))
+            .expect("a synthetic heartbeat should have been emitted");
+        assert_eq!(evt.ntf.method, EventID::HeartbeatEventID);
+    }
+
+    #[test]
+    fn deliver_rearms_the_heartbeat_timer() {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let sub = Subscriber::new(tx);
+        sub.set_heartbeat(HeartbeatFilter { interval_ms: 1000 });
+
+        thread::sleep(TestDuration::from_millis(700));
+        sub.deliver(Notification {
+            jsonrpc: "2.0".to_string(),
+            method: EventID::BlockEventID,
+            params: Vec::new(),
+        })
+        .expect("delivery should succeed");
+
+        let real = rx
+            .recv_timeout(TestDuration::from_millis(100))
+            .expect("the real delivery should arrive");
+        assert_eq!(real.ntf.method, EventID::BlockEventID);
+
+        // The real delivery re-armed the timer, so no heartbeat should
+        // fire in the next 500ms even though 700ms + 500ms > interval_ms.
+        let next = rx.recv_timeout(TestDuration::from_millis(500));
+        assert!(
+            next.is_err(),
+            "heartbeat fired too early after a real delivery re-armed it"
+        );
+    }
+}